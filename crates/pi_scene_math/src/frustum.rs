@@ -1,4 +1,7 @@
-use crate::{plane::Plane, Matrix};
+use crate::{plane::Plane, Matrix, Number, Vector3};
+
+/// from_rect_2d 的深度范围 [-RECT_2D_DEPTH, RECT_2D_DEPTH] - 以有限值代替 "无限制", 使 corners() 结果保持有限
+pub(crate) const RECT_2D_DEPTH: Number = 1_000_000.;
 
 pub struct FrustumPlanes {
     pub near: Plane,
@@ -16,6 +19,40 @@ impl Default for FrustumPlanes {
 }
 
 impl FrustumPlanes {
+    /// 从 视图投影矩阵 提取规范化的六个平面
+    /// 裁剪空间深度范围为 [-1, 1] (nalgebra Orthographic3/Perspective3), near 取 row3 + row2
+    /// 本 crate 的 camera 工具生成的是 [0, 1] 深度的投影, 使用 from_view_projection_zero_to_one
+    pub fn from_view_projection(vp: &Matrix) -> Self {
+        let mut result = Self::default();
+        result.from_transform_matrix(vp);
+        result
+    }
+
+    /// 从 视图投影矩阵 提取规范化的六个平面
+    /// 裁剪空间深度范围为 [0, 1] (wgpu, camera 工具), near 取 row2
+    pub fn from_view_projection_zero_to_one(vp: &Matrix) -> Self {
+        let mut result = Self::default();
+        result.from_transform_matrix(vp);
+        result.transform_near_plane_zero_to_one(vp);
+        result
+    }
+
+    /// 2D 矩形视口 [x, x + w] x [y, y + h] 的四个侧面, near/far 取 z = -1e6 / 1e6, 即深度上近似不做限制
+    /// 按 y 轴向上命名: bottom 在较小的 y, top 在较大的 y; y 向下的屏幕坐标下覆盖区域不变, 仅 top/bottom 名称对调
+    /// w/h 为负时矩形向 x/y 负方向延伸
+    pub fn from_rect_2d(x: Number, y: Number, w: Number, h: Number) -> Self {
+        let (x0, x1) = if w < 0. { (x + w, x) } else { (x, x + w) };
+        let (y0, y1) = if h < 0. { (y + h, y) } else { (y, y + h) };
+        Self {
+            near: Plane { normal: Vector3::new(0., 0., 1.), d: RECT_2D_DEPTH },
+            far: Plane { normal: Vector3::new(0., 0., -1.), d: RECT_2D_DEPTH },
+            left: Plane { normal: Vector3::new(1., 0., 0.), d: -x0 },
+            right: Plane { normal: Vector3::new(-1., 0., 0.), d: x1 },
+            top: Plane { normal: Vector3::new(0., -1., 0.), d: y1 },
+            bottom: Plane { normal: Vector3::new(0., 1., 0.), d: -y0 },
+        }
+    }
+
     /// 使用的 BABYLONJS 代码 行主序
     pub fn from_transform_matrix(&mut self, transform: &Matrix) {
         // Near
@@ -37,6 +74,46 @@ impl FrustumPlanes {
         self.transform_bottom_plane(transform);
     }
 
+    /// 全部六个平面
+    pub fn planes(&self) -> [&Plane; 6] {
+        [&self.near, &self.far, &self.left, &self.right, &self.top, &self.bottom]
+    }
+
+    /// 2D 剔除只需四个侧面, 忽略 near/far
+    pub fn planes_2d(&self) -> [&Plane; 4] {
+        [&self.left, &self.right, &self.top, &self.bottom]
+    }
+
+    /// 八个角点 - 索引 bit0: left/right, bit1: bottom/top, bit2: near/far
+    /// 任一组平面平行无交点时返回 None
+    pub fn try_corners(&self) -> Option<[Vector3; 8]> {
+        let mut result = [Vector3::zeros(); 8];
+        for (i, corner) in result.iter_mut().enumerate() {
+            let x = if i & 1 == 0 { &self.left } else { &self.right };
+            let y = if i & 2 == 0 { &self.bottom } else { &self.top };
+            let z = if i & 4 == 0 { &self.near } else { &self.far };
+            *corner = Plane::intersect_planes(x, y, z)?;
+        }
+        Some(result)
+    }
+
+    /// 八个角点, 顺序同 try_corners
+    /// 平截体退化 (存在平行平面) 时八个角点全部为原点, 需区分时使用 try_corners
+    pub fn corners(&self) -> [Vector3; 8] {
+        self.try_corners().unwrap_or([Vector3::zeros(); 8])
+    }
+
+    /// 规范化六个平面 - 球体等基于有符号距离的检测要求平面法线为单位长度
+    /// 手动构建或修改了平面时, 在进行一批检测前调用一次即可
+    pub fn ensure_normalized(&mut self) {
+        self.near.normalize();
+        self.far.normalize();
+        self.left.normalize();
+        self.right.normalize();
+        self.top.normalize();
+        self.bottom.normalize();
+    }
+
     pub fn transform_near_plane(&mut self, transform: &Matrix) {
         let m = transform;
         self.near.normal.x  = m[3]  + m[2];
@@ -46,6 +123,16 @@ impl FrustumPlanes {
         self.near.normalize();
     }
 
+    /// 深度范围 [0, 1] 时的 near 平面
+    pub fn transform_near_plane_zero_to_one(&mut self, transform: &Matrix) {
+        let m = transform;
+        self.near.normal.x  = m[2];
+        self.near.normal.y  = m[6];
+        self.near.normal.z  = m[10];
+        self.near.d         = m[14];
+        self.near.normalize();
+    }
+
     pub fn transform_far_plane(&mut self, transform: &Matrix) {
         let m = transform;
         self.far.normal.x = m[3] - m[2];
@@ -91,3 +178,153 @@ impl FrustumPlanes {
         self.bottom.normalize();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use crate::{Orthographic3, Vector3, Number, plane::Plane, camera::TOrthographicCameraTool, coordiante_system::CoordinateSytem3};
+
+    use super::{FrustumPlanes, RECT_2D_DEPTH};
+
+    fn is_sphere_outside(planes: &FrustumPlanes, center: &Vector3, radius: Number) -> bool {
+        is_sphere_outside_planes(&planes.planes(), center, radius)
+    }
+
+    fn is_sphere_outside_planes(planes: &[&Plane], center: &Vector3, radius: Number) -> bool {
+        planes.iter().any(|plane| plane.dot_coordinate2(center) <= -radius)
+    }
+
+    #[test]
+    fn test_ensure_normalized() {
+        let mut planes = FrustumPlanes::default();
+        planes.from_transform_matrix(&Orthographic3::new(-10., 10., -10., 10., 1., 100.).to_homogeneous());
+
+        for plane in [&mut planes.near, &mut planes.far, &mut planes.left, &mut planes.right, &mut planes.top, &mut planes.bottom] {
+            plane.normal *= 5.;
+            plane.d *= 5.;
+        }
+        planes.ensure_normalized();
+
+        assert!(is_sphere_outside(&planes, &Vector3::new(100., 0., -50.), 20.));
+        assert!(!is_sphere_outside(&planes, &Vector3::new(0., 0., -50.), 20.));
+        assert!(!is_sphere_outside(&planes, &Vector3::new(25., 0., -50.), 20.));
+    }
+
+    #[test]
+    fn test_from_view_projection() {
+        let vp = Orthographic3::new(-10., 10., -10., 10., -10., 10.).to_homogeneous();
+        let planes = FrustumPlanes::from_view_projection(&vp);
+
+        assert!(!is_sphere_outside(&planes, &Vector3::zeros(), 0.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(100., 0., 0.), 0.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(0., 0., -100.), 0.));
+    }
+
+    #[test]
+    fn test_from_view_projection_zero_to_one() {
+        // 右手系, 看向 -Z, near 在 z = -1, far 在 z = -100
+        let vp = CoordinateSytem3::orthographic_rh(-10., 10., -10., 10., 1., 100.);
+        let planes = FrustumPlanes::from_view_projection_zero_to_one(&vp);
+
+        assert!(!is_sphere_outside(&planes, &Vector3::new(0., 0., -1.5), 0.));
+        assert!(!is_sphere_outside(&planes, &Vector3::new(0., 0., -50.), 0.));
+        assert!(!is_sphere_outside(&planes, &Vector3::new(0., 0., -99.5), 0.));
+        // 相机与 near 之间
+        assert!(is_sphere_outside(&planes, &Vector3::new(0., 0., -0.5), 0.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(0., 0., -100.5), 0.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(100., 0., -50.), 0.));
+
+        // [-1, 1] 的提取方式会保留 near 之前的点
+        let planes = FrustumPlanes::from_view_projection(&vp);
+        assert!(!is_sphere_outside(&planes, &Vector3::new(0., 0., -0.5), 0.));
+    }
+
+    #[test]
+    fn test_planes_2d() {
+        let vp = Orthographic3::new(-10., 10., -10., 10., 1., 100.).to_homogeneous();
+        let planes = FrustumPlanes::from_view_projection(&vp);
+
+        // 超出 far 平面, 3D 剔除, 2D 可见
+        let center = Vector3::new(0., 0., -1000.);
+        assert!(is_sphere_outside(&planes, &center, 1.));
+        assert!(!is_sphere_outside_planes(&planes.planes_2d(), &center, 1.));
+
+        let center = Vector3::new(100., 0., -1000.);
+        assert!(is_sphere_outside_planes(&planes.planes_2d(), &center, 1.));
+    }
+
+    #[test]
+    fn test_corners() {
+        let vp = Orthographic3::new(-2., 2., -1., 1., 1., 10.).to_homogeneous();
+        let corners = FrustumPlanes::from_view_projection(&vp).corners();
+
+        for (i, corner) in corners.iter().enumerate() {
+            let x = if i & 1 == 0 { -2. } else { 2. };
+            let y = if i & 2 == 0 { -1. } else { 1. };
+            let z = if i & 4 == 0 { -1. } else { -10. };
+            assert_relative_eq!(*corner, Vector3::new(x, y, z), epsilon = 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_corners_zero_to_one() {
+        let vp = CoordinateSytem3::orthographic_rh(-2., 2., -1., 1., 1., 10.);
+        let corners = FrustumPlanes::from_view_projection_zero_to_one(&vp).corners();
+
+        for (i, corner) in corners.iter().enumerate() {
+            let x = if i & 1 == 0 { -2. } else { 2. };
+            let y = if i & 2 == 0 { -1. } else { 1. };
+            let z = if i & 4 == 0 { -1. } else { -10. };
+            assert_relative_eq!(*corner, Vector3::new(x, y, z), epsilon = 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_corners_degenerate() {
+        // 默认六个平面法线均为 (0, 1, 0)
+        let planes = FrustumPlanes::default();
+        assert!(planes.try_corners().is_none());
+        assert_eq!(planes.corners(), [Vector3::zeros(); 8]);
+    }
+
+    #[test]
+    fn test_from_rect_2d() {
+        let planes = FrustumPlanes::from_rect_2d(10., 20., 100., 50.);
+
+        assert!(!is_sphere_outside(&planes, &Vector3::new(50., 40., 0.), 1.));
+        assert!(!is_sphere_outside(&planes, &Vector3::new(50., 40., 10000.), 1.));
+        assert!(!is_sphere_outside(&planes, &Vector3::new(9.5, 40., 0.), 1.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(5., 40., 0.), 1.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(115., 40., 0.), 1.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(50., 75., 0.), 1.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(50., 15., 0.), 1.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(50., 40., 2. * RECT_2D_DEPTH), 1.));
+    }
+
+    #[test]
+    fn test_from_rect_2d_negative_size() {
+        let planes = FrustumPlanes::from_rect_2d(110., 70., -100., -50.);
+        let expected = FrustumPlanes::from_rect_2d(10., 20., 100., 50.);
+
+        assert_relative_eq!(planes.left.d, expected.left.d);
+        assert_relative_eq!(planes.right.d, expected.right.d);
+        assert_relative_eq!(planes.top.d, expected.top.d);
+        assert_relative_eq!(planes.bottom.d, expected.bottom.d);
+        assert!(!is_sphere_outside(&planes, &Vector3::new(50., 40., 0.), 1.));
+        assert!(is_sphere_outside(&planes, &Vector3::new(115., 40., 0.), 1.));
+    }
+
+    #[test]
+    fn test_from_rect_2d_corners() {
+        let corners = FrustumPlanes::from_rect_2d(10., 20., 100., 50.).corners();
+
+        for (i, corner) in corners.iter().enumerate() {
+            let x = if i & 1 == 0 { 10. } else { 110. };
+            let y = if i & 2 == 0 { 20. } else { 70. };
+            let z = if i & 4 == 0 { -RECT_2D_DEPTH } else { RECT_2D_DEPTH };
+            assert_relative_eq!(*corner, Vector3::new(x, y, z), epsilon = 0.0001);
+            assert!(corner.iter().all(|v| v.is_finite()));
+        }
+    }
+}
@@ -41,4 +41,46 @@ impl Plane {
         return self.normal.dot(center)
             + self.d;
     }
+
+    /// 三平面交点, 存在平行平面时返回 None
+    /// 平面无需规范化 - 平行判定按三条法线长度的乘积缩放
+    pub fn intersect_planes(p0: &Plane, p1: &Plane, p2: &Plane) -> Option<Vector3> {
+        let c12 = p1.normal.cross(&p2.normal);
+        let denom = p0.normal.dot(&c12);
+        let scale = p0.normal.norm() * p1.normal.norm() * p2.normal.norm();
+        if denom.abs() <= Number::EPSILON * scale {
+            return None;
+        }
+
+        let c20 = p2.normal.cross(&p0.normal);
+        let c01 = p0.normal.cross(&p1.normal);
+        Some((c12 * p0.d + c20 * p1.d + c01 * p2.d) / -denom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use crate::Vector3;
+
+    use super::Plane;
+
+    #[test]
+    fn test_intersect_planes() {
+        // x = 1, y = 2, z = 3, 法线长度远小于 1
+        let s = 0.001;
+        let px = Plane { normal: Vector3::new(s, 0., 0.), d: -s };
+        let py = Plane { normal: Vector3::new(0., s, 0.), d: -2. * s };
+        let pz = Plane { normal: Vector3::new(0., 0., s), d: -3. * s };
+        let point = Plane::intersect_planes(&px, &py, &pz).unwrap();
+        assert_relative_eq!(point, Vector3::new(1., 2., 3.), epsilon = 0.0001);
+
+        // 法线很长的近似平行平面 - denom 为 10, 大于 EPSILON 但相对法线长度可忽略
+        let s = 1000.;
+        let p0 = Plane { normal: Vector3::new(s, 0., 0.), d: 0. };
+        let p1 = Plane { normal: Vector3::new(s, 0.00001, 0.), d: s };
+        let p2 = Plane { normal: Vector3::new(0., 0., s), d: 0. };
+        assert!(Plane::intersect_planes(&p0, &p1, &p2).is_none());
+    }
 }